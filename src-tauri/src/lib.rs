@@ -1,53 +1,187 @@
 use std::fs;
-use std::path::PathBuf;
-use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use lopdf::{Document, Object, ObjectId};
+use serde::Deserialize;
 use tauri::command;
 
+/// How to order source files before they're merged.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+enum SortBy {
+    ModifiedTime,
+    Name,
+    NaturalName,
+    FileSize,
+    Explicit(Vec<String>),
+}
+
+impl Default for SortBy {
+    fn default() -> Self {
+        SortBy::ModifiedTime
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MergeOptions {
+    #[serde(default)]
+    sort: SortBy,
+    #[serde(default)]
+    recursive: bool,
+    /// Append a blank page after any source document with an odd page count, so each original
+    /// document starts on a fresh sheet when the merged output is printed duplex/booklet-style.
+    #[serde(default)]
+    duplex: bool,
+}
+
+/// One run of a natural-sort key: either consecutive digits or consecutive non-digits.
+#[derive(Debug, PartialEq, Eq)]
+enum NaturalChunk {
+    Digits(u64),
+    Text(String),
+}
+
+/// Splits a file name into alternating runs of digits and non-digits, so e.g. `"page2.pdf"` and
+/// `"page10.pdf"` compare numerically on the digit run instead of lexically.
+fn natural_key(name: &str) -> Vec<NaturalChunk> {
+    let mut chunks = Vec::new();
+    let mut chars = name.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        let mut run = String::new();
+        let is_digit_run = c.is_ascii_digit();
+        while let Some(&c) = chars.peek() {
+            if c.is_ascii_digit() != is_digit_run {
+                break;
+            }
+            run.push(c);
+            chars.next();
+        }
+        chunks.push(if is_digit_run {
+            NaturalChunk::Digits(run.parse().unwrap_or(0))
+        } else {
+            NaturalChunk::Text(run)
+        });
+    }
+
+    chunks
+}
+
+fn natural_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+
+    let (a_chunks, b_chunks) = (natural_key(a), natural_key(b));
+    for (a_chunk, b_chunk) in a_chunks.iter().zip(b_chunks.iter()) {
+        let ord = match (a_chunk, b_chunk) {
+            (NaturalChunk::Digits(x), NaturalChunk::Digits(y)) => x.cmp(y),
+            (NaturalChunk::Text(x), NaturalChunk::Text(y)) => x.cmp(y),
+            (NaturalChunk::Digits(x), NaturalChunk::Text(y)) => x.to_string().cmp(y),
+            (NaturalChunk::Text(x), NaturalChunk::Digits(y)) => x.cmp(&y.to_string()),
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+
+    a_chunks.len().cmp(&b_chunks.len())
+}
+
+/// Collects every `*.pdf` path under `dir`, descending into subdirectories when `recursive` is set.
+fn collect_pdf_paths(dir: &Path, recursive: bool) -> Result<Vec<PathBuf>, String> {
+    let mut visited = HashSet::new();
+    collect_pdf_paths_visiting(dir, recursive, &mut visited)
+}
+
+/// Does the work for `collect_pdf_paths`, tracking canonicalized directory paths already
+/// descended into so a symlink cycle (including one pointing back to an ancestor, or to itself)
+/// gets skipped instead of recursing forever.
+fn collect_pdf_paths_visiting(dir: &Path, recursive: bool, visited: &mut HashSet<PathBuf>) -> Result<Vec<PathBuf>, String> {
+    if let Ok(canonical) = dir.canonicalize() {
+        if !visited.insert(canonical) {
+            return Ok(Vec::new());
+        }
+    }
+
+    let mut result = Vec::new();
+
+    for entry in fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory: {}", e))?
+        .filter_map(|entry| entry.ok())
+    {
+        let path = entry.path();
+        if path.is_dir() {
+            if recursive {
+                result.extend(collect_pdf_paths_visiting(&path, recursive, visited)?);
+            }
+        } else if path.extension().and_then(|ext| ext.to_str()) == Some("pdf") {
+            result.push(path);
+        }
+    }
+
+    Ok(result)
+}
+
+fn sort_pdf_files(pdf_files: &mut [PathBuf], sort: &SortBy) {
+    match sort {
+        SortBy::ModifiedTime => pdf_files.sort_by_key(|path| {
+            path.metadata()
+                .and_then(|m| m.modified())
+                .unwrap_or(std::time::SystemTime::UNIX_EPOCH)
+        }),
+        SortBy::Name => pdf_files.sort_by(|a, b| a.file_name().cmp(&b.file_name())),
+        SortBy::NaturalName => pdf_files.sort_by(|a, b| {
+            let a_name = a.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            let b_name = b.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            natural_cmp(a_name, b_name)
+        }),
+        SortBy::FileSize => pdf_files.sort_by_key(|path| path.metadata().map(|m| m.len()).unwrap_or(0)),
+        SortBy::Explicit(order) => {
+            let index_of = |path: &PathBuf| -> usize {
+                let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+                order
+                    .iter()
+                    .position(|p| p == name || p == &path.to_string_lossy())
+                    .unwrap_or(usize::MAX)
+            };
+            pdf_files.sort_by_key(index_of);
+        }
+    }
+}
+
 #[command]
-fn count_pdfs(dir_path: String) -> Result<usize, String> {
+fn count_pdfs(dir_path: String, recursive: Option<bool>) -> Result<usize, String> {
     let dir = PathBuf::from(&dir_path);
     if !dir.is_dir() {
         return Err(format!("Directory '{}' does not exist.", dir_path));
     }
 
-    let pdf_count = fs::read_dir(&dir)
-        .map_err(|e| format!("Failed to read directory: {}", e))?
-        .filter_map(|entry| entry.ok())
-        .filter(|entry| {
-            entry.path().extension().and_then(|ext| ext.to_str()) == Some("pdf")
-        })
-        .count();
+    let pdf_count = collect_pdf_paths(&dir, recursive.unwrap_or(false))?.len();
 
     Ok(pdf_count)
 }
 
 #[command]
-fn merge_pdfs(dir_path: String) -> Result<String, String> {
+fn merge_pdfs(
+    dir_path: String,
+    title: Option<String>,
+    author: Option<String>,
+    options: Option<MergeOptions>,
+) -> Result<String, String> {
     let dir = PathBuf::from(&dir_path);
     if !dir.is_dir() {
         return Err(format!("Directory '{}' does not exist.", dir_path));
     }
+    let options = options.unwrap_or_default();
 
-    let mut pdf_files: Vec<_> = fs::read_dir(&dir)
-        .map_err(|e| format!("Failed to read directory: {}", e))?
-        .filter_map(|entry| entry.ok())
-        .filter(|entry| {
-            entry.path().extension().and_then(|ext| ext.to_str()) == Some("pdf")
-        })
-        .map(|entry| entry.path())
-        .collect();
+    let mut pdf_files = collect_pdf_paths(&dir, options.recursive)?;
 
     if pdf_files.is_empty() {
         return Err("No PDF files found in the directory.".to_string());
     }
 
-    // Sort by modification time
-    pdf_files.sort_by_key(|path| {
-        path.metadata()
-            .and_then(|m| m.modified())
-            .unwrap_or(std::time::SystemTime::UNIX_EPOCH)
-    });
+    sort_pdf_files(&mut pdf_files, &options.sort);
 
     let output_filename = format!("{}.pdf", dir.file_name().unwrap().to_str().unwrap());
     let output_path = dir.parent().unwrap().join(&output_filename);
@@ -65,7 +199,14 @@ fn merge_pdfs(dir_path: String) -> Result<String, String> {
     let mut merged_doc = Document::with_version("1.5");
     let mut max_id = 1u32;
     let mut id_maps: Vec<BTreeMap<ObjectId, ObjectId>> = Vec::with_capacity(documents.len());
-    
+
+    // Dedup bookkeeping, shared across all source documents: each hash bucket holds the
+    // canonical bytes and new id of every distinct object seen so far that hashes to it (so a
+    // collision never gets treated as a match), and `dedup_remap` maps the new id of every
+    // later-seen duplicate to the first new id that produced its canonical form.
+    let mut content_hashes: HashMap<u64, Vec<(Vec<u8>, ObjectId)>> = HashMap::new();
+    let mut dedup_remap: BTreeMap<ObjectId, ObjectId> = BTreeMap::new();
+
     // Two-pass per document: build full mapping, then copy with updates
     for doc in &documents {
         let mut id_map = BTreeMap::new();
@@ -77,31 +218,132 @@ fn merge_pdfs(dir_path: String) -> Result<String, String> {
             id_map.insert(old_id, new_id);
         }
 
-        // 2) Copy objects with reference fixups using the full map
+        // 2) Copy objects with reference fixups using the full map, deduplicating identical
+        // shared objects (fonts, images, ...) so we don't keep N copies of the same resource.
+        // Pages are structurally distinct per source, so never dedup those. Each source's own
+        // Pages-tree root and Catalog are dropped entirely: `merge_pdfs` builds a single new
+        // tree and catalog once every document has been copied in, so the originals would just
+        // be unreferenced dead weight in the output.
         for (&old_id, object) in &doc.objects {
             let mut obj = object.clone();
             update_references(&mut obj, &id_map);
             let new_id = id_map[&old_id];
-            merged_doc.objects.insert(new_id, obj);
+
+            if is_superseded_root(&obj) {
+                continue;
+            }
+
+            if is_structural_object(&obj) {
+                merged_doc.objects.insert(new_id, obj);
+                continue;
+            }
+
+            let canonical = canonical_bytes(&obj);
+            match dedup_lookup(&mut content_hashes, canonical, new_id) {
+                Some(canonical_id) => {
+                    dedup_remap.insert(new_id, canonical_id);
+                }
+                None => {
+                    merged_doc.objects.insert(new_id, obj);
+                }
+            }
         }
 
         id_maps.push(id_map);
     }
-    
+
+    // Point every reference at a deduplicated object's id directly at its surviving canonical copy.
+    if !dedup_remap.is_empty() {
+        for obj in merged_doc.objects.values_mut() {
+            update_references(obj, &dedup_remap);
+        }
+    }
+
+    // The pass above only dedups leaf objects (no internal references), since a non-leaf object's
+    // hash still embeds this-document's freshly-allocated ids for whatever it references. Now
+    // that those nested references have been rewritten to their canonical ids, re-hash and repeat
+    // until a round finds nothing new to merge, so non-leaf shared objects (e.g. a `Resources`
+    // dict referencing an already-deduped `Font`) collapse too.
+    loop {
+        let mut round_hashes: HashMap<u64, Vec<(Vec<u8>, ObjectId)>> = HashMap::new();
+        let mut round_remap: BTreeMap<ObjectId, ObjectId> = BTreeMap::new();
+
+        for (&id, obj) in merged_doc.objects.iter() {
+            if is_structural_object(obj) {
+                continue;
+            }
+            let canonical = canonical_bytes(obj);
+            if let Some(canonical_id) = dedup_lookup(&mut round_hashes, canonical, id) {
+                round_remap.insert(id, canonical_id);
+            }
+        }
+
+        if round_remap.is_empty() {
+            break;
+        }
+
+        for id in round_remap.keys() {
+            merged_doc.objects.remove(id);
+        }
+        for obj in merged_doc.objects.values_mut() {
+            update_references(obj, &round_remap);
+        }
+        dedup_remap.extend(round_remap);
+    }
+
+    // A later round can remap an id that an earlier round already used as someone else's
+    // canonical target (A -> B in round 1, then B -> C in round 2). Collapse those chains so a
+    // single lookup in `dedup_remap` always lands on the final surviving id — `merged_doc.objects`
+    // itself was already fully resolved by each round's sweep, but `dedup_remap` is also applied
+    // standalone later (see `apply_inherited_attributes`), where only one hop would otherwise run.
+    let chained_keys: Vec<ObjectId> = dedup_remap.keys().copied().collect();
+    for key in chained_keys {
+        let mut target = dedup_remap[&key];
+        while let Some(&next) = dedup_remap.get(&target) {
+            target = next;
+        }
+        dedup_remap.insert(key, target);
+    }
+
     // Collect all page references
     let mut all_page_ids = Vec::new();
+    let mut first_page_ids = Vec::with_capacity(documents.len());
+    let mut blank_content_id = None;
     for (doc_idx, doc) in documents.iter().enumerate() {
         let pages = doc.get_pages();
         let mut page_list: Vec<_> = pages.into_iter().collect();
         page_list.sort_by(|a, b| a.0.cmp(&b.0));
-        
+
+        let mut doc_first_page_id = None;
+        let mut doc_page_ids = Vec::new();
         for (_, old_page_id) in page_list {
             if let Some(&new_page_id) = id_maps[doc_idx].get(&old_page_id) {
+                apply_inherited_attributes(doc, old_page_id, &id_maps[doc_idx], &dedup_remap, &mut merged_doc, new_page_id);
                 all_page_ids.push(new_page_id);
+                doc_first_page_id.get_or_insert(new_page_id);
+                doc_page_ids.push(new_page_id);
+            }
+        }
+        if let Some(first_page_id) = doc_first_page_id {
+            // Pair with this document's own path (not positionally with `pdf_files`), so a
+            // source with zero pages doesn't shift every later outline title out of alignment.
+            first_page_ids.push((pdf_files[doc_idx].clone(), first_page_id));
+        }
+
+        if options.duplex && doc_page_ids.len() % 2 == 1 {
+            if let Some(&last_page_id) = doc_page_ids.last() {
+                let content_id = *blank_content_id.get_or_insert_with(|| {
+                    let id = (max_id, 0);
+                    max_id += 1;
+                    merged_doc.objects.insert(id, Object::Stream(lopdf::Stream::new(lopdf::Dictionary::new(), Vec::new())));
+                    id
+                });
+                let blank_page_id = add_blank_page(&mut merged_doc, last_page_id, content_id, &mut max_id);
+                all_page_ids.push(blank_page_id);
             }
         }
     }
-    
+
     // Create new page tree
     let pages_id = (max_id, 0);
     max_id += 1;
@@ -131,11 +373,41 @@ fn merge_pdfs(dir_path: String) -> Result<String, String> {
     let mut catalog = lopdf::Dictionary::new();
     catalog.set("Type", Object::Name(b"Catalog".to_vec()));
     catalog.set("Pages", Object::Reference(pages_id));
-    
+
+    // Build a bookmark outline with one entry per source file, so viewers show a
+    // table-of-contents sidebar linking straight to where each document begins.
+    if let Some(outlines_id) = build_outlines(&mut merged_doc, &first_page_ids, &mut max_id) {
+        catalog.set("Outlines", Object::Reference(outlines_id));
+    }
+
     merged_doc.objects.insert(catalog_id, Object::Dictionary(catalog));
-    
+
+    // Write an Info dictionary so viewers show real metadata instead of a blank Title/Author.
+    let info_id = (max_id, 0);
+    max_id += 1;
+
+    let default_title = output_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Merged Document")
+        .to_string();
+    let now = chrono::Local::now().format("D:%Y%m%d%H%M%S").to_string();
+
+    let mut info = lopdf::Dictionary::new();
+    info.set("Title", literal_string(title.unwrap_or(default_title)));
+    if let Some(author) = author {
+        info.set("Author", literal_string(author));
+    }
+    info.set("Creator", literal_string("PDF Mergery"));
+    info.set("Producer", literal_string("PDF Mergery"));
+    info.set("CreationDate", literal_string(now.clone()));
+    info.set("ModDate", literal_string(now));
+
+    merged_doc.objects.insert(info_id, Object::Dictionary(info));
+
     // Set trailer
     merged_doc.trailer.set("Root", Object::Reference(catalog_id));
+    merged_doc.trailer.set("Info", Object::Reference(info_id));
     merged_doc.max_id = max_id;
     
     // Save the merged document
@@ -145,6 +417,291 @@ fn merge_pdfs(dir_path: String) -> Result<String, String> {
     Ok(output_path.to_string_lossy().to_string())
 }
 
+/// Page attributes that PDF allows a `Page` to inherit from an ancestor `Pages` node
+/// instead of defining directly (see ISO 32000-1 7.7.3.4).
+const INHERITABLE_PAGE_KEYS: [&[u8]; 4] = [b"Resources", b"MediaBox", b"CropBox", b"Rotate"];
+
+/// Walks `page_id`'s `Parent` chain in `source_doc` and returns the inheritable attributes
+/// (`Resources`, `MediaBox`, `CropBox`, `Rotate`) taken from the nearest ancestor that defines
+/// each one. Keys already on the page itself are left for the caller to skip.
+fn collect_inherited_attributes(source_doc: &Document, page_id: ObjectId) -> lopdf::Dictionary {
+    let mut inherited = lopdf::Dictionary::new();
+
+    let mut parent_id = source_doc
+        .get_dictionary(page_id)
+        .ok()
+        .and_then(|page_dict| page_dict.get(b"Parent").ok())
+        .and_then(|obj| obj.as_reference().ok());
+
+    while let Some(current_id) = parent_id {
+        let Ok(parent_dict) = source_doc.get_dictionary(current_id) else {
+            break;
+        };
+
+        for &key in &INHERITABLE_PAGE_KEYS {
+            if inherited.get(key).is_err() {
+                if let Ok(value) = parent_dict.get(key) {
+                    inherited.set(key, value.clone());
+                }
+            }
+        }
+
+        parent_id = parent_dict.get(b"Parent").ok().and_then(|obj| obj.as_reference().ok());
+    }
+
+    inherited
+}
+
+/// Fills in any inheritable attributes (`Resources`, `MediaBox`, `CropBox`, `Rotate`) that
+/// `new_page_id` doesn't already define, resolved from its ancestors in the original source
+/// document. The chain is walked in `source_doc`, since the source `Pages` nodes are not copied
+/// into `merged_doc` and the page is later reparented under the merged `Pages` node.
+///
+/// Runs the fixed-up values through both `id_map` (old doc id -> new merged id) and `dedup_remap`
+/// (new id -> surviving canonical id), since this is inserted after the blanket dedup sweep over
+/// `merged_doc.objects` and would otherwise keep a dangling reference to a deduplicated-away id.
+fn apply_inherited_attributes(
+    source_doc: &Document,
+    old_page_id: ObjectId,
+    id_map: &BTreeMap<ObjectId, ObjectId>,
+    dedup_remap: &BTreeMap<ObjectId, ObjectId>,
+    merged_doc: &mut Document,
+    new_page_id: ObjectId,
+) {
+    let inherited = collect_inherited_attributes(source_doc, old_page_id);
+    if inherited.is_empty() {
+        return;
+    }
+
+    let mut inherited_obj = Object::Dictionary(inherited);
+    update_references(&mut inherited_obj, id_map);
+    update_references(&mut inherited_obj, dedup_remap);
+
+    let Object::Dictionary(inherited) = inherited_obj else {
+        unreachable!()
+    };
+
+    if let Some(Object::Dictionary(ref mut page_dict)) = merged_doc.objects.get_mut(&new_page_id) {
+        for (key, value) in inherited.iter() {
+            if page_dict.get(key).is_err() {
+                page_dict.set(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+fn literal_string(s: impl Into<Vec<u8>>) -> Object {
+    Object::String(s.into(), lopdf::StringFormat::Literal)
+}
+
+/// Appends an empty filler page (`MediaBox` copied from `preceding_page_id`, `Contents` pointing
+/// at the shared empty `content_id` stream) to `merged_doc`, the way `mkbookpdf` pads a document
+/// to an even page count for duplex printing. Its `Parent` is left unset here; the caller's
+/// final reparent-to-`pages_id` pass fills it in.
+fn add_blank_page(merged_doc: &mut Document, preceding_page_id: ObjectId, content_id: ObjectId, max_id: &mut u32) -> ObjectId {
+    let media_box = merged_doc
+        .objects
+        .get(&preceding_page_id)
+        .and_then(|obj| obj.as_dict().ok())
+        .and_then(|dict| dict.get(b"MediaBox").ok())
+        .cloned();
+
+    let blank_page_id = (*max_id, 0);
+    *max_id += 1;
+
+    let mut blank_page = lopdf::Dictionary::new();
+    blank_page.set("Type", Object::Name(b"Page".to_vec()));
+    blank_page.set("Contents", Object::Reference(content_id));
+    if let Some(media_box) = media_box {
+        blank_page.set("MediaBox", media_box);
+    }
+
+    merged_doc.objects.insert(blank_page_id, Object::Dictionary(blank_page));
+    blank_page_id
+}
+
+/// Builds an `Outlines` tree with one top-level bookmark per source file that contributed at
+/// least one page, each pointing at that file's first page. `first_page_ids` pairs each such
+/// file's own path with its first page id, so a source with zero pages (a valid but page-less
+/// PDF) simply has no entry instead of shifting every later title out of alignment. Returns the
+/// id of the `Outlines` root, or `None` if there are no pages to link to.
+fn build_outlines(
+    merged_doc: &mut Document,
+    first_page_ids: &[(PathBuf, ObjectId)],
+    max_id: &mut u32,
+) -> Option<ObjectId> {
+    if first_page_ids.is_empty() {
+        return None;
+    }
+
+    let item_ids: Vec<ObjectId> = first_page_ids
+        .iter()
+        .map(|_| {
+            let id = (*max_id, 0);
+            *max_id += 1;
+            id
+        })
+        .collect();
+
+    let outlines_id = (*max_id, 0);
+    *max_id += 1;
+
+    for (i, (path, page_id)) in first_page_ids.iter().enumerate() {
+        let title = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("Untitled")
+            .to_string();
+
+        let mut item = lopdf::Dictionary::new();
+        item.set("Title", literal_string(title));
+        item.set("Dest", Object::Array(vec![Object::Reference(*page_id), Object::Name(b"Fit".to_vec())]));
+        item.set("Parent", Object::Reference(outlines_id));
+        if i > 0 {
+            item.set("Prev", Object::Reference(item_ids[i - 1]));
+        }
+        if i + 1 < item_ids.len() {
+            item.set("Next", Object::Reference(item_ids[i + 1]));
+        }
+
+        merged_doc.objects.insert(item_ids[i], Object::Dictionary(item));
+    }
+
+    let mut outlines = lopdf::Dictionary::new();
+    outlines.set("Type", Object::Name(b"Outlines".to_vec()));
+    outlines.set("First", Object::Reference(item_ids[0]));
+    outlines.set("Last", Object::Reference(*item_ids.last().unwrap()));
+    outlines.set("Count", Object::Integer(item_ids.len() as i64));
+
+    merged_doc.objects.insert(outlines_id, Object::Dictionary(outlines));
+
+    Some(outlines_id)
+}
+
+/// Whether `object` is a single page: pages must stay distinct even if two sources happen to
+/// produce byte-identical dictionaries, so they're never deduplicated.
+fn is_structural_object(object: &Object) -> bool {
+    if let Object::Dictionary(dict) = object {
+        if let Ok(Object::Name(name)) = dict.get(b"Type") {
+            return name.as_slice() == b"Page";
+        }
+    }
+    false
+}
+
+/// Whether `object` is a source document's own page-tree root or document catalog. Both are
+/// superseded by the single new tree and catalog `merge_pdfs` builds once every source has
+/// been copied in, so they're dropped rather than carried into the merged document.
+fn is_superseded_root(object: &Object) -> bool {
+    if let Object::Dictionary(dict) = object {
+        if let Ok(Object::Name(name)) = dict.get(b"Type") {
+            return matches!(name.as_slice(), b"Pages" | b"Catalog");
+        }
+    }
+    false
+}
+
+/// Canonicalizes an object (after its references have already been fixed up) into bytes that are
+/// identical for two objects with identical content, so shared resources like embedded fonts or
+/// images copied from multiple source files can be collapsed into a single shared copy. Unlike
+/// `format!("{:?}", object)`, this sorts dictionary entries instead of preserving insertion order
+/// and, for streams, only looks at the dict and raw content — never lopdf's runtime bookkeeping
+/// (`Stream::start_position`, `allows_compression`), which differs even for byte-identical
+/// streams read from two independently-authored source files.
+fn canonical_bytes(object: &Object) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_canonical(object, &mut buf);
+    buf
+}
+
+fn write_canonical(object: &Object, buf: &mut Vec<u8>) {
+    match object {
+        Object::Null => buf.push(0),
+        Object::Boolean(b) => {
+            buf.push(1);
+            buf.push(*b as u8);
+        }
+        Object::Integer(i) => {
+            buf.push(2);
+            buf.extend_from_slice(&i.to_le_bytes());
+        }
+        Object::Real(f) => {
+            buf.push(3);
+            buf.extend_from_slice(&f.to_le_bytes());
+        }
+        Object::Name(name) => {
+            buf.push(4);
+            write_canonical_bytes(name, buf);
+        }
+        Object::String(s, format) => {
+            buf.push(5);
+            buf.push(match format {
+                lopdf::StringFormat::Literal => 0,
+                lopdf::StringFormat::Hexadecimal => 1,
+            });
+            write_canonical_bytes(s, buf);
+        }
+        Object::Array(items) => {
+            buf.push(6);
+            buf.extend_from_slice(&(items.len() as u64).to_le_bytes());
+            for item in items {
+                write_canonical(item, buf);
+            }
+        }
+        Object::Dictionary(dict) => {
+            buf.push(7);
+            write_canonical_dict(dict, buf);
+        }
+        Object::Stream(stream) => {
+            buf.push(8);
+            write_canonical_dict(&stream.dict, buf);
+            write_canonical_bytes(&stream.content, buf);
+        }
+        Object::Reference(id) => {
+            buf.push(9);
+            buf.extend_from_slice(&id.0.to_le_bytes());
+            buf.extend_from_slice(&id.1.to_le_bytes());
+        }
+    }
+}
+
+fn write_canonical_bytes(bytes: &[u8], buf: &mut Vec<u8>) {
+    buf.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+    buf.extend_from_slice(bytes);
+}
+
+fn write_canonical_dict(dict: &lopdf::Dictionary, buf: &mut Vec<u8>) {
+    let mut entries: Vec<(&Vec<u8>, &Object)> = dict.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    buf.extend_from_slice(&(entries.len() as u64).to_le_bytes());
+    for (key, value) in entries {
+        write_canonical_bytes(key, buf);
+        write_canonical(value, buf);
+    }
+}
+
+/// Looks up `canonical` among objects already seen this pass. A hash collision alone is never
+/// enough to call two objects the same — `table` keeps the full canonical bytes alongside each
+/// hash bucket so a hit is confirmed by real equality before any reference gets remapped onto it.
+/// Returns the canonical id of a real duplicate, or records `candidate_id` as canonical and
+/// returns `None`.
+fn dedup_lookup(
+    table: &mut HashMap<u64, Vec<(Vec<u8>, ObjectId)>>,
+    canonical: Vec<u8>,
+    candidate_id: ObjectId,
+) -> Option<ObjectId> {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    canonical.hash(&mut hasher);
+    let hash = hasher.finish();
+
+    let bucket = table.entry(hash).or_default();
+    if let Some(&(_, existing_id)) = bucket.iter().find(|(bytes, _)| *bytes == canonical) {
+        return Some(existing_id);
+    }
+    bucket.push((canonical, candidate_id));
+    None
+}
+
 fn update_references(object: &mut Object, id_map: &BTreeMap<ObjectId, ObjectId>) {
     match object {
         Object::Reference(ref mut id) => {
@@ -242,6 +799,153 @@ mod tests {
         doc
     }
 
+    fn create_pdf_with_pages(page_count: usize) -> Document {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+
+        let mut page_refs = Vec::with_capacity(page_count);
+        for _ in 0..page_count {
+            let content_id = doc.new_object_id();
+            let page_id = doc.new_object_id();
+
+            let content = lopdf::Stream::new(lopdf::Dictionary::new(), b"BT ET".to_vec());
+            doc.objects.insert(content_id, Object::Stream(content));
+
+            let mut page = lopdf::Dictionary::new();
+            page.set("Type", Object::Name(b"Page".to_vec()));
+            page.set("Parent", Object::Reference(pages_id));
+            page.set("Contents", Object::Reference(content_id));
+            page.set("MediaBox", Object::Array(vec![
+                Object::Integer(0), Object::Integer(0),
+                Object::Integer(612), Object::Integer(792)
+            ]));
+            doc.objects.insert(page_id, Object::Dictionary(page));
+
+            page_refs.push(Object::Reference(page_id));
+        }
+
+        let mut pages = lopdf::Dictionary::new();
+        pages.set("Type", Object::Name(b"Pages".to_vec()));
+        pages.set("Count", Object::Integer(page_count as i64));
+        pages.set("Kids", Object::Array(page_refs));
+        doc.objects.insert(pages_id, Object::Dictionary(pages));
+
+        let catalog_id = doc.add_object(lopdf::Dictionary::from_iter(vec![
+            ("Type", Object::Name(b"Catalog".to_vec())),
+            ("Pages", Object::Reference(pages_id)),
+        ]));
+
+        doc.trailer.set("Root", Object::Reference(catalog_id));
+        doc
+    }
+
+    /// A single page that defines no `Resources`/`MediaBox` of its own and inherits both from
+    /// its `Pages` ancestor via an indirect reference, matching the layout `chunk0-1` targets.
+    fn create_pdf_with_inherited_resources() -> Document {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let page_id = doc.new_object_id();
+        let resources_id = doc.new_object_id();
+        let content_id = doc.new_object_id();
+
+        doc.objects.insert(resources_id, Object::Dictionary(lopdf::Dictionary::new()));
+        doc.objects.insert(content_id, Object::Stream(lopdf::Stream::new(lopdf::Dictionary::new(), b"BT ET".to_vec())));
+
+        let mut page = lopdf::Dictionary::new();
+        page.set("Type", Object::Name(b"Page".to_vec()));
+        page.set("Parent", Object::Reference(pages_id));
+        page.set("Contents", Object::Reference(content_id));
+        doc.objects.insert(page_id, Object::Dictionary(page));
+
+        let mut pages = lopdf::Dictionary::new();
+        pages.set("Type", Object::Name(b"Pages".to_vec()));
+        pages.set("Kids", Object::Array(vec![Object::Reference(page_id)]));
+        pages.set("Count", Object::Integer(1));
+        pages.set("Resources", Object::Reference(resources_id));
+        pages.set("MediaBox", Object::Array(vec![
+            Object::Integer(0), Object::Integer(0),
+            Object::Integer(612), Object::Integer(792)
+        ]));
+        doc.objects.insert(pages_id, Object::Dictionary(pages));
+
+        let catalog_id = doc.add_object(lopdf::Dictionary::from_iter(vec![
+            ("Type", Object::Name(b"Catalog".to_vec())),
+            ("Pages", Object::Reference(pages_id)),
+        ]));
+
+        doc.trailer.set("Root", Object::Reference(catalog_id));
+        doc
+    }
+
+    /// A one-page document embedding the same Helvetica font as `create_minimal_pdf`, but built
+    /// with a different id layout and dict key order: when `padded` is set, an unrelated leading
+    /// object shifts every later object's on-disk byte offset, and the font dict's keys are set
+    /// in reverse order. Two documents from this helper with different `padded` values should
+    /// still dedup their shared font, even though their saved bytes (and lopdf's runtime
+    /// `Stream::start_position`) differ.
+    fn create_pdf_with_shared_font(padded: bool) -> Document {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let page_id = doc.new_object_id();
+
+        if padded {
+            let padding_id = doc.new_object_id();
+            doc.objects.insert(padding_id, Object::Stream(lopdf::Stream::new(lopdf::Dictionary::new(), b"unrelated padding".to_vec())));
+        }
+
+        let font_id = doc.new_object_id();
+        let resources_id = doc.new_object_id();
+        let content_id = doc.new_object_id();
+
+        let mut font = lopdf::Dictionary::new();
+        if padded {
+            font.set("BaseFont", Object::Name(b"Helvetica".to_vec()));
+            font.set("Subtype", Object::Name(b"Type1".to_vec()));
+            font.set("Type", Object::Name(b"Font".to_vec()));
+        } else {
+            font.set("Type", Object::Name(b"Font".to_vec()));
+            font.set("Subtype", Object::Name(b"Type1".to_vec()));
+            font.set("BaseFont", Object::Name(b"Helvetica".to_vec()));
+        }
+        doc.objects.insert(font_id, Object::Dictionary(font));
+
+        let mut fonts = lopdf::Dictionary::new();
+        fonts.set("F1", Object::Reference(font_id));
+        let mut resources = lopdf::Dictionary::new();
+        resources.set("Font", Object::Dictionary(fonts));
+        doc.objects.insert(resources_id, Object::Dictionary(resources));
+
+        doc.objects.insert(content_id, Object::Stream(lopdf::Stream::new(
+            lopdf::Dictionary::new(),
+            b"BT /F1 12 Tf 100 700 Td (Test) Tj ET".to_vec()
+        )));
+
+        let mut page = lopdf::Dictionary::new();
+        page.set("Type", Object::Name(b"Page".to_vec()));
+        page.set("Parent", Object::Reference(pages_id));
+        page.set("Contents", Object::Reference(content_id));
+        page.set("Resources", Object::Reference(resources_id));
+        page.set("MediaBox", Object::Array(vec![
+            Object::Integer(0), Object::Integer(0),
+            Object::Integer(612), Object::Integer(792)
+        ]));
+        doc.objects.insert(page_id, Object::Dictionary(page));
+
+        let mut pages = lopdf::Dictionary::new();
+        pages.set("Type", Object::Name(b"Pages".to_vec()));
+        pages.set("Kids", Object::Array(vec![Object::Reference(page_id)]));
+        pages.set("Count", Object::Integer(1));
+        doc.objects.insert(pages_id, Object::Dictionary(pages));
+
+        let catalog_id = doc.add_object(lopdf::Dictionary::from_iter(vec![
+            ("Type", Object::Name(b"Catalog".to_vec())),
+            ("Pages", Object::Reference(pages_id)),
+        ]));
+
+        doc.trailer.set("Root", Object::Reference(catalog_id));
+        doc
+    }
+
     #[test]
     fn test_merge_pdfs_success() {
         let temp_dir = TempDir::new().unwrap();
@@ -253,7 +957,7 @@ mod tests {
         pdf1.save(dir_path.join("test1.pdf")).unwrap();
         pdf2.save(dir_path.join("test2.pdf")).unwrap();
 
-        let result = merge_pdfs(dir_path.to_string_lossy().to_string());
+        let result = merge_pdfs(dir_path.to_string_lossy().to_string(), None, None, None);
         assert!(result.is_ok());
 
         let output_path = result.unwrap();
@@ -299,7 +1003,7 @@ mod tests {
 
     #[test]
     fn test_merge_pdfs_directory_not_found() {
-        let result = merge_pdfs("/nonexistent/directory".to_string());
+        let result = merge_pdfs("/nonexistent/directory".to_string(), None, None, None);
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("does not exist"));
     }
@@ -309,7 +1013,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let dir_path = temp_dir.path();
 
-        let result = merge_pdfs(dir_path.to_string_lossy().to_string());
+        let result = merge_pdfs(dir_path.to_string_lossy().to_string(), None, None, None);
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("No PDF files found"));
     }
@@ -324,13 +1028,169 @@ mod tests {
         pdf.save(dir_path.join("test2.pdf")).unwrap();
         fs::write(dir_path.join("not_a_pdf.txt"), "test").unwrap();
 
-        let result = count_pdfs(dir_path.to_string_lossy().to_string());
+        let result = count_pdfs(dir_path.to_string_lossy().to_string(), None);
         assert_eq!(result.unwrap(), 2);
     }
 
     #[test]
     fn test_count_pdfs_directory_not_found() {
-        let result = count_pdfs("/nonexistent/directory".to_string());
+        let result = count_pdfs("/nonexistent/directory".to_string(), None);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_merge_pdfs_dedups_shared_objects() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir_path = temp_dir.path();
+
+        let naive_sum: usize = {
+            let mut pdf1 = create_minimal_pdf();
+            let mut pdf2 = create_minimal_pdf();
+            pdf1.save(dir_path.join("test1.pdf")).unwrap();
+            pdf2.save(dir_path.join("test2.pdf")).unwrap();
+            Document::load(dir_path.join("test1.pdf")).unwrap().objects.len()
+                + Document::load(dir_path.join("test2.pdf")).unwrap().objects.len()
+        };
+
+        let output_path = merge_pdfs(dir_path.to_string_lossy().to_string(), None, None, None).unwrap();
+        let merged = Document::load(&output_path).unwrap();
+
+        assert!(
+            merged.objects.len() < naive_sum,
+            "merged object count ({}) should be less than the naive sum ({}) once shared objects are deduplicated",
+            merged.objects.len(),
+            naive_sum
+        );
+    }
+
+    #[test]
+    fn test_merge_pdfs_dedups_shared_font_across_differently_structured_documents() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir_path = temp_dir.path();
+
+        // Same Helvetica font, but the second document has an extra leading object (shifting
+        // every later object's saved byte offset) and a font dict with keys in reverse order, so
+        // a naive Debug-string hash of the raw objects would not see them as identical.
+        let mut pdf1 = create_pdf_with_shared_font(false);
+        let mut pdf2 = create_pdf_with_shared_font(true);
+        pdf1.save(dir_path.join("test1.pdf")).unwrap();
+        pdf2.save(dir_path.join("test2.pdf")).unwrap();
+
+        let output_path = merge_pdfs(dir_path.to_string_lossy().to_string(), None, None, None).unwrap();
+        let merged = Document::load(&output_path).unwrap();
+
+        let font_ids: HashSet<ObjectId> = merged
+            .get_pages()
+            .values()
+            .map(|&page_id| {
+                let page = merged.get_dictionary(page_id).unwrap();
+                let resources = merged.get_dictionary(page.get(b"Resources").unwrap().as_reference().unwrap()).unwrap();
+                let fonts = resources.get(b"Font").unwrap().as_dict().unwrap();
+                fonts.get(b"F1").unwrap().as_reference().unwrap()
+            })
+            .collect();
+
+        assert_eq!(font_ids.len(), 1, "the two documents' identical Helvetica fonts should collapse to a single shared object");
+    }
+
+    #[test]
+    fn test_merge_pdfs_duplex_pads_odd_documents() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir_path = temp_dir.path();
+
+        let mut pdf1 = create_pdf_with_pages(1);
+        let mut pdf2 = create_pdf_with_pages(2);
+        pdf1.save(dir_path.join("test1.pdf")).unwrap();
+        pdf2.save(dir_path.join("test2.pdf")).unwrap();
+
+        // Pin name order rather than relying on the default modified-time sort: two files saved
+        // back-to-back in a temp dir can land on identical mtimes, leaving the fallback order to
+        // `fs::read_dir`'s unspecified enumeration.
+        let options = MergeOptions { sort: SortBy::Name, duplex: true, ..Default::default() };
+        let output_path = merge_pdfs(dir_path.to_string_lossy().to_string(), None, None, Some(options)).unwrap();
+
+        let merged = Document::load(&output_path).unwrap();
+        assert_eq!(merged.get_pages().len(), 4);
+    }
+
+    #[test]
+    fn test_merge_pdfs_resolves_inherited_resources_after_dedup() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir_path = temp_dir.path();
+
+        // Both documents inherit an identical (and thus deduplicated-away) Resources dict from
+        // their Pages ancestor; the merged page's inherited reference must still resolve.
+        let mut pdf1 = create_pdf_with_inherited_resources();
+        let mut pdf2 = create_pdf_with_inherited_resources();
+        pdf1.save(dir_path.join("test1.pdf")).unwrap();
+        pdf2.save(dir_path.join("test2.pdf")).unwrap();
+
+        let output_path = merge_pdfs(dir_path.to_string_lossy().to_string(), None, None, None).unwrap();
+        let merged = Document::load(&output_path).unwrap();
+
+        for (_, page_id) in merged.get_pages() {
+            let page_dict = merged.get_dictionary(page_id).unwrap();
+            match page_dict.get(b"Resources") {
+                Ok(Object::Reference(ref_id)) => {
+                    assert!(
+                        merged.get_object(*ref_id).is_ok(),
+                        "inherited Resources reference should resolve to a real object, not a deduplicated-away id"
+                    );
+                }
+                other => panic!("expected an inherited indirect Resources reference, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_pdfs_outline_titles_skip_pageless_documents() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir_path = temp_dir.path();
+
+        let mut empty_doc = create_pdf_with_pages(0);
+        let mut doc_b = create_pdf_with_pages(1);
+        let mut doc_c = create_pdf_with_pages(2);
+        empty_doc.save(dir_path.join("aaa_empty.pdf")).unwrap();
+        doc_b.save(dir_path.join("bbb_one.pdf")).unwrap();
+        doc_c.save(dir_path.join("ccc_two.pdf")).unwrap();
+
+        let options = MergeOptions { sort: SortBy::Name, ..Default::default() };
+        let output_path = merge_pdfs(dir_path.to_string_lossy().to_string(), None, None, Some(options)).unwrap();
+        let merged = Document::load(&output_path).unwrap();
+
+        let root_ref = merged.trailer.get(b"Root").unwrap().as_reference().unwrap();
+        let catalog = merged.get_dictionary(root_ref).unwrap();
+        let outlines_ref = catalog.get(b"Outlines").unwrap().as_reference().unwrap();
+        let outlines = merged.get_dictionary(outlines_ref).unwrap();
+        let first_ref = outlines.get(b"First").unwrap().as_reference().unwrap();
+        let first_item = merged.get_dictionary(first_ref).unwrap();
+
+        // The empty document ("aaa_empty") contributed no page, so the first outline entry
+        // (and the page it links to) must belong to "bbb_one", not be shifted off by one.
+        match first_item.get(b"Title").unwrap() {
+            Object::String(bytes, _) => assert_eq!(bytes.as_slice(), b"bbb_one"),
+            other => panic!("expected a Title string, got {:?}", other),
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_count_pdfs_recursive_handles_symlink_cycle() {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new().unwrap();
+        let dir_path = temp_dir.path();
+
+        let mut pdf = create_minimal_pdf();
+        pdf.save(dir_path.join("test1.pdf")).unwrap();
+
+        // A subdirectory symlinked back to an ancestor would recurse forever without cycle
+        // detection in `collect_pdf_paths`.
+        let sub_dir = dir_path.join("sub");
+        fs::create_dir(&sub_dir).unwrap();
+        symlink(dir_path, sub_dir.join("loop")).unwrap();
+
+        let result = count_pdfs(dir_path.to_string_lossy().to_string(), Some(true));
+        assert_eq!(result.unwrap(), 1);
+    }
 }